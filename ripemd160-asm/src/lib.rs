@@ -0,0 +1,110 @@
+//! RIPEMD-160 compression function used as the `asm` backend.
+//!
+//! This crate is selected by the `ripemd160` crate's `asm` feature, the
+//! same way `md5-asm`/`sha1-asm` back the `md-5`/`sha-1` crates. The
+//! implementation is portable Rust with the same round structure as the
+//! `ripemd160` crate's own pure-Rust compression function; it contains no
+//! assembly.
+#![no_std]
+
+const R: [[usize; 16]; 5] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8],
+    [3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12],
+    [1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2],
+    [4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13],
+];
+
+const RR: [[usize; 16]; 5] = [
+    [5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12],
+    [6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2],
+    [15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13],
+    [8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14],
+    [12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11],
+];
+
+const S: [[u32; 16]; 5] = [
+    [11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8],
+    [7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12],
+    [11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5],
+    [11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12],
+    [9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6],
+];
+
+const SS: [[u32; 16]; 5] = [
+    [8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6],
+    [9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11],
+    [9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5],
+    [15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8],
+    [8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11],
+];
+
+const K: [u32; 5] = [0x0000_0000, 0x5a82_7999, 0x6ed9_eba1, 0x8f1b_bcdc, 0xa953_fd4e];
+const KK: [u32; 5] = [0x50a2_8be6, 0x5c4d_d124, 0x6d70_3ef3, 0x7a6d_76e9, 0x0000_0000];
+
+fn left_f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+fn right_f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ (y | !z),
+        1 => (x & z) | (y & !z),
+        2 => (x | !y) ^ z,
+        3 => (x & y) | (!x & z),
+        _ => x ^ y ^ z,
+    }
+}
+
+/// Compress a single RIPEMD-160 message block, updating `state` in place.
+pub fn compress(state: &mut [u32; 5], block: &[u8; 64]) {
+    let mut data = [0u32; 16];
+    for (o, chunk) in data.iter_mut().zip(block.chunks_exact(4)) {
+        *o = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+
+    let h = state;
+    let (mut a, mut b, mut c, mut d, mut e) = (h[0], h[1], h[2], h[3], h[4]);
+    let (mut aa, mut bb, mut cc, mut dd, mut ee) = (h[0], h[1], h[2], h[3], h[4]);
+
+    for round in 0..5 {
+        for step in 0..16 {
+            let t = a
+                .wrapping_add(left_f(round, b, c, d))
+                .wrapping_add(data[R[round][step]])
+                .wrapping_add(K[round])
+                .rotate_left(S[round][step])
+                .wrapping_add(e);
+            a = e;
+            e = d;
+            d = c.rotate_left(10);
+            c = b;
+            b = t;
+
+            let tt = aa
+                .wrapping_add(right_f(round, bb, cc, dd))
+                .wrapping_add(data[RR[round][step]])
+                .wrapping_add(KK[round])
+                .rotate_left(SS[round][step])
+                .wrapping_add(ee);
+            aa = ee;
+            ee = dd;
+            dd = cc.rotate_left(10);
+            cc = bb;
+            bb = tt;
+        }
+    }
+
+    let t = h[1].wrapping_add(c).wrapping_add(dd);
+    h[1] = h[2].wrapping_add(d).wrapping_add(ee);
+    h[2] = h[3].wrapping_add(e).wrapping_add(aa);
+    h[3] = h[4].wrapping_add(a).wrapping_add(bb);
+    h[4] = h[0].wrapping_add(b).wrapping_add(cc);
+    h[0] = t;
+}
@@ -0,0 +1,340 @@
+use digest::generic_array::GenericArray;
+use digest::generic_array::typenum::U64;
+
+type Block = GenericArray<u8, U64>;
+
+/// Number of 32-bit words in the RIPEMD-128 digest buffer.
+pub const DIGEST_BUF_LEN_128: usize = 4;
+/// Number of 32-bit words in the RIPEMD-160 digest buffer.
+pub const DIGEST_BUF_LEN: usize = 5;
+/// Number of 32-bit words in the RIPEMD-256 digest buffer.
+pub const DIGEST_BUF_LEN_256: usize = 8;
+/// Number of 32-bit words in the RIPEMD-320 digest buffer.
+pub const DIGEST_BUF_LEN_320: usize = 10;
+
+pub const H0_128: [u32; DIGEST_BUF_LEN_128] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+
+pub const H0: [u32; DIGEST_BUF_LEN] =
+    [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476, 0xc3d2_e1f0];
+
+pub const H0_256: [u32; DIGEST_BUF_LEN_256] = [
+    0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476,
+    0x7654_3210, 0xfedc_ba98, 0x89ab_cdef, 0x0123_4567,
+];
+
+pub const H0_320: [u32; DIGEST_BUF_LEN_320] = [
+    0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476, 0xc3d2_e1f0,
+    0x7654_3210, 0xfedc_ba98, 0x89ab_cdef, 0x0123_4567, 0x3c2d_1e0f,
+];
+
+// Message word selection order for each of the 5 rounds (16 steps each),
+// shared between the 128/256 line (first 4 rounds) and the 160/320 line
+// (all 5 rounds).
+const R: [[usize; 16]; 5] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8],
+    [3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12],
+    [1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2],
+    [4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13],
+];
+
+const RR: [[usize; 16]; 5] = [
+    [5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12],
+    [6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2],
+    [15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13],
+    [8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14],
+    [12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11],
+];
+
+// Rotate-left amounts, same sharing rule as `R`/`RR` above.
+const S: [[u32; 16]; 5] = [
+    [11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8],
+    [7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12],
+    [11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5],
+    [11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12],
+    [9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6],
+];
+
+const SS: [[u32; 16]; 5] = [
+    [8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6],
+    [9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11],
+    [9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5],
+    [15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8],
+    [8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11],
+];
+
+// Additive round constants, left line. RIPEMD-128/256 use the first 4.
+const K: [u32; 5] = [0x0000_0000, 0x5a82_7999, 0x6ed9_eba1, 0x8f1b_bcdc, 0xa953_fd4e];
+
+// Additive round constants, right line, for the 160/320 (5-round) variant.
+const KK: [u32; 5] = [0x50a2_8be6, 0x5c4d_d124, 0x6d70_3ef3, 0x7a6d_76e9, 0x0000_0000];
+
+// Additive round constants, right line, for the 128/256 (4-round) variant.
+// Identical to `KK` except the last round constant is zero instead of
+// 0x7a6d76e9.
+const KK128: [u32; 4] = [0x50a2_8be6, 0x5c4d_d124, 0x6d70_3ef3, 0x0000_0000];
+
+#[inline(always)]
+fn f1(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
+
+#[inline(always)]
+fn f2(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (!x & z)
+}
+
+#[inline(always)]
+fn f3(x: u32, y: u32, z: u32) -> u32 {
+    (x | !y) ^ z
+}
+
+#[inline(always)]
+fn f4(x: u32, y: u32, z: u32) -> u32 {
+    (x & z) | (y & !z)
+}
+
+#[inline(always)]
+fn f5(x: u32, y: u32, z: u32) -> u32 {
+    x ^ (y | !z)
+}
+
+#[inline(always)]
+fn left_f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => f1(x, y, z),
+        1 => f2(x, y, z),
+        2 => f3(x, y, z),
+        3 => f4(x, y, z),
+        _ => f5(x, y, z),
+    }
+}
+
+#[inline(always)]
+fn right_f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => f5(x, y, z),
+        1 => f4(x, y, z),
+        2 => f3(x, y, z),
+        3 => f2(x, y, z),
+        _ => f1(x, y, z),
+    }
+}
+
+// The 4-round (RIPEMD-128/256) right line runs its round functions in
+// reverse order starting from `f4`, not `f5` -- `f5` only exists in the
+// 5-round (RIPEMD-160/320) schedule.
+#[inline(always)]
+fn right_f4(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => f4(x, y, z),
+        1 => f3(x, y, z),
+        2 => f2(x, y, z),
+        _ => f1(x, y, z),
+    }
+}
+
+#[inline(always)]
+fn read_words(block: &Block) -> [u32; 16] {
+    let mut words = [0u32; 16];
+    for (o, chunk) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *o = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    words
+}
+
+/// Process a single message block, updating the 5-word RIPEMD-160 state.
+///
+/// Unused when the `asm` feature replaces this as the `Ripemd160` backend
+/// and `compress` isn't also enabled to re-export it directly.
+#[cfg_attr(all(feature = "asm", not(feature = "compress")), allow(dead_code))]
+pub fn process_msg_block(h: &mut [u32; DIGEST_BUF_LEN], block: &Block) {
+    let data = read_words(block);
+
+    let (mut a, mut b, mut c, mut d, mut e) = (h[0], h[1], h[2], h[3], h[4]);
+    let (mut aa, mut bb, mut cc, mut dd, mut ee) = (h[0], h[1], h[2], h[3], h[4]);
+
+    for round in 0..5 {
+        for step in 0..16 {
+            let t = a
+                .wrapping_add(left_f(round, b, c, d))
+                .wrapping_add(data[R[round][step]])
+                .wrapping_add(K[round])
+                .rotate_left(S[round][step])
+                .wrapping_add(e);
+            a = e;
+            e = d;
+            d = c.rotate_left(10);
+            c = b;
+            b = t;
+
+            let tt = aa
+                .wrapping_add(right_f(round, bb, cc, dd))
+                .wrapping_add(data[RR[round][step]])
+                .wrapping_add(KK[round])
+                .rotate_left(SS[round][step])
+                .wrapping_add(ee);
+            aa = ee;
+            ee = dd;
+            dd = cc.rotate_left(10);
+            cc = bb;
+            bb = tt;
+        }
+    }
+
+    let t = h[1].wrapping_add(c).wrapping_add(dd);
+    h[1] = h[2].wrapping_add(d).wrapping_add(ee);
+    h[2] = h[3].wrapping_add(e).wrapping_add(aa);
+    h[3] = h[4].wrapping_add(a).wrapping_add(bb);
+    h[4] = h[0].wrapping_add(b).wrapping_add(cc);
+    h[0] = t;
+}
+
+/// Process a single message block, updating the 4-word RIPEMD-128 state.
+pub fn process_msg_block128(h: &mut [u32; DIGEST_BUF_LEN_128], block: &Block) {
+    let data = read_words(block);
+
+    let (mut a, mut b, mut c, mut d) = (h[0], h[1], h[2], h[3]);
+    let (mut aa, mut bb, mut cc, mut dd) = (h[0], h[1], h[2], h[3]);
+
+    for round in 0..4 {
+        for step in 0..16 {
+            let t = a
+                .wrapping_add(left_f(round, b, c, d))
+                .wrapping_add(data[R[round][step]])
+                .wrapping_add(K[round])
+                .rotate_left(S[round][step]);
+            a = d;
+            d = c;
+            c = b;
+            b = t;
+
+            let tt = aa
+                .wrapping_add(right_f4(round, bb, cc, dd))
+                .wrapping_add(data[RR[round][step]])
+                .wrapping_add(KK128[round])
+                .rotate_left(SS[round][step]);
+            aa = dd;
+            dd = cc;
+            cc = bb;
+            bb = tt;
+        }
+    }
+
+    let t = h[1].wrapping_add(c).wrapping_add(dd);
+    h[1] = h[2].wrapping_add(d).wrapping_add(aa);
+    h[2] = h[3].wrapping_add(a).wrapping_add(bb);
+    h[3] = h[0].wrapping_add(b).wrapping_add(cc);
+    h[0] = t;
+}
+
+/// Process a single message block, updating the 8-word RIPEMD-256 state.
+///
+/// RIPEMD-256 runs the two RIPEMD-128 lines side by side, but instead of
+/// combining them at the end it swaps one chaining word between the lines
+/// after each of the 4 rounds and emits both lines concatenated.
+pub fn process_msg_block256(h: &mut [u32; DIGEST_BUF_LEN_256], block: &Block) {
+    let data = read_words(block);
+
+    let (mut a, mut b, mut c, mut d) = (h[0], h[1], h[2], h[3]);
+    let (mut aa, mut bb, mut cc, mut dd) = (h[4], h[5], h[6], h[7]);
+
+    for round in 0..4 {
+        for step in 0..16 {
+            let t = a
+                .wrapping_add(left_f(round, b, c, d))
+                .wrapping_add(data[R[round][step]])
+                .wrapping_add(K[round])
+                .rotate_left(S[round][step]);
+            a = d;
+            d = c;
+            c = b;
+            b = t;
+
+            let tt = aa
+                .wrapping_add(right_f4(round, bb, cc, dd))
+                .wrapping_add(data[RR[round][step]])
+                .wrapping_add(KK128[round])
+                .rotate_left(SS[round][step]);
+            aa = dd;
+            dd = cc;
+            cc = bb;
+            bb = tt;
+        }
+
+        match round {
+            0 => core::mem::swap(&mut a, &mut aa),
+            1 => core::mem::swap(&mut b, &mut bb),
+            2 => core::mem::swap(&mut c, &mut cc),
+            _ => core::mem::swap(&mut d, &mut dd),
+        }
+    }
+
+    h[0] = h[0].wrapping_add(a);
+    h[1] = h[1].wrapping_add(b);
+    h[2] = h[2].wrapping_add(c);
+    h[3] = h[3].wrapping_add(d);
+    h[4] = h[4].wrapping_add(aa);
+    h[5] = h[5].wrapping_add(bb);
+    h[6] = h[6].wrapping_add(cc);
+    h[7] = h[7].wrapping_add(dd);
+}
+
+/// Process a single message block, updating the 10-word RIPEMD-320 state.
+///
+/// RIPEMD-320 is the analogous extension of RIPEMD-160: the two lines stay
+/// independent, swap one chaining word after each of the 5 rounds, and are
+/// emitted concatenated rather than combined.
+pub fn process_msg_block320(h: &mut [u32; DIGEST_BUF_LEN_320], block: &Block) {
+    let data = read_words(block);
+
+    let (mut a, mut b, mut c, mut d, mut e) = (h[0], h[1], h[2], h[3], h[4]);
+    let (mut aa, mut bb, mut cc, mut dd, mut ee) = (h[5], h[6], h[7], h[8], h[9]);
+
+    for round in 0..5 {
+        for step in 0..16 {
+            let t = a
+                .wrapping_add(left_f(round, b, c, d))
+                .wrapping_add(data[R[round][step]])
+                .wrapping_add(K[round])
+                .rotate_left(S[round][step])
+                .wrapping_add(e);
+            a = e;
+            e = d;
+            d = c.rotate_left(10);
+            c = b;
+            b = t;
+
+            let tt = aa
+                .wrapping_add(right_f(round, bb, cc, dd))
+                .wrapping_add(data[RR[round][step]])
+                .wrapping_add(KK[round])
+                .rotate_left(SS[round][step])
+                .wrapping_add(ee);
+            aa = ee;
+            ee = dd;
+            dd = cc.rotate_left(10);
+            cc = bb;
+            bb = tt;
+        }
+
+        match round {
+            0 => core::mem::swap(&mut b, &mut bb),
+            1 => core::mem::swap(&mut d, &mut dd),
+            2 => core::mem::swap(&mut a, &mut aa),
+            3 => core::mem::swap(&mut c, &mut cc),
+            _ => core::mem::swap(&mut e, &mut ee),
+        }
+    }
+
+    h[0] = h[0].wrapping_add(a);
+    h[1] = h[1].wrapping_add(b);
+    h[2] = h[2].wrapping_add(c);
+    h[3] = h[3].wrapping_add(d);
+    h[4] = h[4].wrapping_add(e);
+    h[5] = h[5].wrapping_add(aa);
+    h[6] = h[6].wrapping_add(bb);
+    h[7] = h[7].wrapping_add(cc);
+    h[8] = h[8].wrapping_add(dd);
+    h[9] = h[9].wrapping_add(ee);
+}
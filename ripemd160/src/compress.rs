@@ -0,0 +1,15 @@
+use block::{process_msg_block, DIGEST_BUF_LEN};
+use digest::generic_array::GenericArray;
+
+/// Raw RIPEMD-160 compression function.
+///
+/// Processes each of `blocks` in turn, updating `state` in place. This is
+/// the same permutation `Ripemd160` drives internally through its
+/// `Digest`/`BlockBuffer` wrapper; it is exposed directly for advanced users
+/// who maintain their own buffering, experiment with length-extension, or
+/// embed RIPEMD-160 inside a larger protocol.
+pub fn compress(state: &mut [u32; DIGEST_BUF_LEN], blocks: &[[u8; 64]]) {
+    for block in blocks {
+        process_msg_block(state, GenericArray::from_slice(block));
+    }
+}
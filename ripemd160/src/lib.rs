@@ -1,4 +1,5 @@
-//! An implementation of the RIPEMD-160 cryptographic hash.
+//! An implementation of the RIPEMD family of cryptographic hashes: RIPEMD-128,
+//! RIPEMD-160, RIPEMD-256 and RIPEMD-320.
 //!
 //! # Usage
 //!
@@ -26,16 +27,54 @@ extern crate block_buffer;
 #[macro_use] pub extern crate digest;
 #[cfg(feature = "std")]
 extern crate std;
+#[cfg(feature = "asm")]
+extern crate ripemd160_asm;
+#[cfg(test)]
+#[macro_use]
+extern crate hex_literal;
 
 pub use digest::Digest;
 use digest::{Input, BlockInput, FixedOutput, Reset};
 use block_buffer::BlockBuffer;
 use block_buffer::byteorder::{LE, ByteOrder};
 use digest::generic_array::GenericArray;
-use digest::generic_array::typenum::{U20, U64};
+use digest::generic_array::typenum::{U16, U20, U32, U40, U64};
 
 mod block;
-use block::{process_msg_block, DIGEST_BUF_LEN, H0};
+#[cfg(not(feature = "asm"))]
+use block::process_msg_block;
+use block::{DIGEST_BUF_LEN, H0};
+use block::{process_msg_block128, DIGEST_BUF_LEN_128, H0_128};
+use block::{process_msg_block256, DIGEST_BUF_LEN_256, H0_256};
+use block::{process_msg_block320, DIGEST_BUF_LEN_320, H0_320};
+
+#[cfg(feature = "compress")]
+mod compress;
+#[cfg(feature = "compress")]
+pub use compress::compress;
+
+mod self_test;
+pub use self_test::{self_test, SelfTestError};
+#[cfg(all(feature = "std", feature = "self-test-on-first-use"))]
+pub use self_test::ensure_self_test;
+
+/// Compress a single RIPEMD-160 message block, updating `state` in place.
+///
+/// Dispatches to the pure-Rust implementation by default, or to the
+/// `ripemd160-asm` backend when the `asm` feature is enabled. Both
+/// implementations are verified against the same test vectors, so callers
+/// can treat them as interchangeable.
+#[cfg(not(feature = "asm"))]
+fn compress_block(state: &mut [u32; DIGEST_BUF_LEN], block: &GenericArray<u8, U64>) {
+    process_msg_block(state, block);
+}
+
+#[cfg(feature = "asm")]
+fn compress_block(state: &mut [u32; DIGEST_BUF_LEN], block: &GenericArray<u8, U64>) {
+    let mut buf = [0u8; 64];
+    buf.copy_from_slice(block.as_slice());
+    ripemd160_asm::compress(state, &buf);
+}
 
 /// Structure representing the state of a Ripemd160 computation
 #[derive(Clone)]
@@ -60,11 +99,12 @@ impl BlockInput for Ripemd160 {
 }
 
 impl Input for Ripemd160 {
-    fn process(&mut self, input: &[u8]) {
+    fn input<B: AsRef<[u8]>>(&mut self, input: B) {
+        let input = input.as_ref();
         // Assumes that input.len() can be converted to u64 without overflow
         self.len += input.len() as u64;
         let h = &mut self.h;
-        self.buffer.input(input, |b| process_msg_block(h, b));
+        self.buffer.input(input, |b| compress_block(h, b));
     }
 }
 
@@ -75,7 +115,7 @@ impl FixedOutput for Ripemd160 {
         {
             let h = &mut self.h;
             let l = self.len << 3;
-            self.buffer.len64_padding::<LE, _>(l, |b| process_msg_block(h, b));
+            self.buffer.len64_padding::<LE, _>(l, |b| compress_block(h, b));
         }
 
         let mut out = GenericArray::default();
@@ -85,14 +125,255 @@ impl FixedOutput for Ripemd160 {
 }
 
 impl Reset for Ripemd160 {
-    fn reset(&mut self) -> Self {
-        let temp = self.clone();
+    fn reset(&mut self) {
         self.buffer.reset();
         self.len = 0;
         self.h = H0;
-        temp
     }
 }
 
 impl_opaque_debug!(Ripemd160);
 impl_write!(Ripemd160);
+
+/// Structure representing the state of a Ripemd128 computation
+#[derive(Clone)]
+pub struct Ripemd128 {
+    h: [u32; DIGEST_BUF_LEN_128],
+    len: u64,
+    buffer: BlockBuffer<U64>,
+}
+
+impl Default for Ripemd128 {
+    fn default() -> Self {
+        Ripemd128 {
+            h: H0_128,
+            len: 0,
+            buffer: Default::default(),
+        }
+    }
+}
+
+impl BlockInput for Ripemd128 {
+    type BlockSize = U64;
+}
+
+impl Input for Ripemd128 {
+    fn input<B: AsRef<[u8]>>(&mut self, input: B) {
+        let input = input.as_ref();
+        // Assumes that input.len() can be converted to u64 without overflow
+        self.len += input.len() as u64;
+        let h = &mut self.h;
+        self.buffer.input(input, |b| process_msg_block128(h, b));
+    }
+}
+
+impl FixedOutput for Ripemd128 {
+    type OutputSize = U16;
+
+    fn fixed_result(mut self) -> GenericArray<u8, Self::OutputSize> {
+        {
+            let h = &mut self.h;
+            let l = self.len << 3;
+            self.buffer.len64_padding::<LE, _>(l, |b| process_msg_block128(h, b));
+        }
+
+        let mut out = GenericArray::default();
+        LE::write_u32_into(&self.h, &mut out[..]);
+        out
+    }
+}
+
+impl Reset for Ripemd128 {
+    fn reset(&mut self) {
+        self.buffer.reset();
+        self.len = 0;
+        self.h = H0_128;
+    }
+}
+
+impl_opaque_debug!(Ripemd128);
+impl_write!(Ripemd128);
+
+/// Structure representing the state of a Ripemd256 computation
+#[derive(Clone)]
+pub struct Ripemd256 {
+    h: [u32; DIGEST_BUF_LEN_256],
+    len: u64,
+    buffer: BlockBuffer<U64>,
+}
+
+impl Default for Ripemd256 {
+    fn default() -> Self {
+        Ripemd256 {
+            h: H0_256,
+            len: 0,
+            buffer: Default::default(),
+        }
+    }
+}
+
+impl BlockInput for Ripemd256 {
+    type BlockSize = U64;
+}
+
+impl Input for Ripemd256 {
+    fn input<B: AsRef<[u8]>>(&mut self, input: B) {
+        let input = input.as_ref();
+        // Assumes that input.len() can be converted to u64 without overflow
+        self.len += input.len() as u64;
+        let h = &mut self.h;
+        self.buffer.input(input, |b| process_msg_block256(h, b));
+    }
+}
+
+impl FixedOutput for Ripemd256 {
+    type OutputSize = U32;
+
+    fn fixed_result(mut self) -> GenericArray<u8, Self::OutputSize> {
+        {
+            let h = &mut self.h;
+            let l = self.len << 3;
+            self.buffer.len64_padding::<LE, _>(l, |b| process_msg_block256(h, b));
+        }
+
+        let mut out = GenericArray::default();
+        LE::write_u32_into(&self.h, &mut out[..]);
+        out
+    }
+}
+
+impl Reset for Ripemd256 {
+    fn reset(&mut self) {
+        self.buffer.reset();
+        self.len = 0;
+        self.h = H0_256;
+    }
+}
+
+impl_opaque_debug!(Ripemd256);
+impl_write!(Ripemd256);
+
+/// Structure representing the state of a Ripemd320 computation
+#[derive(Clone)]
+pub struct Ripemd320 {
+    h: [u32; DIGEST_BUF_LEN_320],
+    len: u64,
+    buffer: BlockBuffer<U64>,
+}
+
+impl Default for Ripemd320 {
+    fn default() -> Self {
+        Ripemd320 {
+            h: H0_320,
+            len: 0,
+            buffer: Default::default(),
+        }
+    }
+}
+
+impl BlockInput for Ripemd320 {
+    type BlockSize = U64;
+}
+
+impl Input for Ripemd320 {
+    fn input<B: AsRef<[u8]>>(&mut self, input: B) {
+        let input = input.as_ref();
+        // Assumes that input.len() can be converted to u64 without overflow
+        self.len += input.len() as u64;
+        let h = &mut self.h;
+        self.buffer.input(input, |b| process_msg_block320(h, b));
+    }
+}
+
+impl FixedOutput for Ripemd320 {
+    type OutputSize = U40;
+
+    fn fixed_result(mut self) -> GenericArray<u8, Self::OutputSize> {
+        {
+            let h = &mut self.h;
+            let l = self.len << 3;
+            self.buffer.len64_padding::<LE, _>(l, |b| process_msg_block320(h, b));
+        }
+
+        let mut out = GenericArray::default();
+        LE::write_u32_into(&self.h, &mut out[..]);
+        out
+    }
+}
+
+impl Reset for Ripemd320 {
+    fn reset(&mut self) {
+        self.buffer.reset();
+        self.len = 0;
+        self.h = H0_320;
+    }
+}
+
+impl_opaque_debug!(Ripemd320);
+impl_write!(Ripemd320);
+
+#[cfg(test)]
+mod tests {
+    use super::{Ripemd128, Ripemd256, Ripemd320};
+    use digest::Digest;
+
+    // Known-answer tests covering each sibling type's own round tables and
+    // combination formula; `self_test` only exercises Ripemd160.
+    macro_rules! kat_test {
+        ($name:ident, $hasher:ty, $input:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                let mut hasher = <$hasher>::new();
+                hasher.input($input);
+                assert_eq!(&hasher.result()[..], &$expected[..]);
+            }
+        };
+    }
+
+    kat_test!(ripemd128_empty, Ripemd128, b"", hex!("cdf26213a150dc3ecb610f18f6b38b46"));
+    kat_test!(ripemd128_abc, Ripemd128, b"abc", hex!("c14a12199c66e4ba84636b0f69144c77"));
+    kat_test!(
+        ripemd128_message_digest,
+        Ripemd128,
+        b"message digest",
+        hex!("9e327b3d6e523062afc1132d7df9d1b8")
+    );
+
+    kat_test!(
+        ripemd256_empty,
+        Ripemd256,
+        b"",
+        hex!("02ba4c4e5f8ecd1877fc52d64d30e37a2d9774fb1e5d026380ae0168e3c5522d")
+    );
+    kat_test!(
+        ripemd256_abc,
+        Ripemd256,
+        b"abc",
+        hex!("afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65")
+    );
+    kat_test!(
+        ripemd256_message_digest,
+        Ripemd256,
+        b"message digest",
+        hex!("87e971759a1ce47a514d5c914c392c9018c7c46bc14465554afcdf54a5070c0e")
+    );
+
+    kat_test!(
+        ripemd320_empty,
+        Ripemd320,
+        b"",
+        hex!("22d65d5661536cdc75c1fdf5c6de7b41b9f27325ebc61e8557177d705a0ec880151c3a32a00899b8")
+    );
+    kat_test!(
+        ripemd320_abc,
+        Ripemd320,
+        b"abc",
+        hex!("de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82fa942d64cdbc4682d")
+    );
+    kat_test!(
+        ripemd320_message_digest,
+        Ripemd320,
+        b"message digest",
+        hex!("3a8e28502ed45d422f68844f9dd316e7b98533fa3f2a91d29f84d425c88d6b4eff727df66a7c0197")
+    );
+}
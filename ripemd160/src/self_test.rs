@@ -0,0 +1,150 @@
+//! Built-in self-test against the canonical RIPEMD-160 test vectors.
+//!
+//! Libraries with FIPS-style power-on self-test requirements (the cryptlib
+//! implementation ships exactly such a vector table) can call [`self_test`]
+//! at startup to confirm that the build -- including any `asm` backend --
+//! still produces the correct RIPEMD-160 digests.
+
+use core::fmt;
+use digest::Digest;
+use Ripemd160;
+
+/// A self-test vector failed to reproduce its expected digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfTestError {
+    /// Human-readable description of the vector that failed.
+    pub vector: &'static str,
+}
+
+impl fmt::Display for SelfTestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RIPEMD-160 self-test vector \"{}\" produced an unexpected digest", self.vector)
+    }
+}
+
+enum Input {
+    Bytes(&'static [u8]),
+    Repeated(&'static [u8], usize),
+}
+
+struct Vector {
+    name: &'static str,
+    input: Input,
+    expected: [u8; 20],
+}
+
+const VECTORS: &[Vector] = &[
+    Vector {
+        name: "",
+        input: Input::Bytes(b""),
+        expected: [
+            0x9c, 0x11, 0x85, 0xa5, 0xc5, 0xe9, 0xfc, 0x54, 0x61, 0x28,
+            0x08, 0x97, 0x7e, 0xe8, 0xf5, 0x48, 0xb2, 0x25, 0x8d, 0x31,
+        ],
+    },
+    Vector {
+        name: "a",
+        input: Input::Bytes(b"a"),
+        expected: [
+            0x0b, 0xdc, 0x9d, 0x2d, 0x25, 0x6b, 0x3e, 0xe9, 0xda, 0xae,
+            0x34, 0x7b, 0xe6, 0xf4, 0xdc, 0x83, 0x5a, 0x46, 0x7f, 0xfe,
+        ],
+    },
+    Vector {
+        name: "abc",
+        input: Input::Bytes(b"abc"),
+        expected: [
+            0x8e, 0xb2, 0x08, 0xf7, 0xe0, 0x5d, 0x98, 0x7a, 0x9b, 0x04,
+            0x4a, 0x8e, 0x98, 0xc6, 0xb0, 0x87, 0xf1, 0x5a, 0x0b, 0xfc,
+        ],
+    },
+    Vector {
+        name: "message digest",
+        input: Input::Bytes(b"message digest"),
+        expected: [
+            0x5d, 0x06, 0x89, 0xef, 0x49, 0xd2, 0xfa, 0xe5, 0x72, 0xb8,
+            0x81, 0xb1, 0x23, 0xa8, 0x5f, 0xfa, 0x21, 0x59, 0x5f, 0x36,
+        ],
+    },
+    Vector {
+        name: "abcdefghijklmnopqrstuvwxyz",
+        input: Input::Bytes(b"abcdefghijklmnopqrstuvwxyz"),
+        expected: [
+            0xf7, 0x1c, 0x27, 0x10, 0x9c, 0x69, 0x2c, 0x1b, 0x56, 0xbb,
+            0xdc, 0xeb, 0x5b, 0x9d, 0x28, 0x65, 0xb3, 0x70, 0x8d, 0xbc,
+        ],
+    },
+    Vector {
+        name: "8 times \"1234567890\"",
+        input: Input::Repeated(b"1234567890", 8),
+        expected: [
+            0x9b, 0x75, 0x2e, 0x45, 0x57, 0x3d, 0x4b, 0x39, 0xf4, 0xdb,
+            0xd3, 0x32, 0x3c, 0xab, 0x82, 0xbf, 0x63, 0x32, 0x6b, 0xfb,
+        ],
+    },
+    Vector {
+        name: "1 million repetitions of \"a\"",
+        input: Input::Repeated(b"a", 1_000_000),
+        expected: [
+            0x52, 0x78, 0x32, 0x43, 0xc1, 0x69, 0x7b, 0xdb, 0xe1, 0x6d,
+            0x37, 0xf9, 0x7f, 0x68, 0xf0, 0x83, 0x25, 0xdc, 0x15, 0x28,
+        ],
+    },
+];
+
+fn check(vector: &Vector) -> Result<(), SelfTestError> {
+    let mut hasher = Ripemd160::new();
+    match vector.input {
+        Input::Bytes(b) => hasher.input(b),
+        Input::Repeated(b, n) => {
+            for _ in 0..n {
+                hasher.input(b);
+            }
+        }
+    }
+    if hasher.result()[..] == vector.expected[..] {
+        Ok(())
+    } else {
+        Err(SelfTestError { vector: vector.name })
+    }
+}
+
+/// Run the canonical RIPEMD-160 reference vectors and return an error for
+/// the first digest that does not match its expected constant.
+pub fn self_test() -> Result<(), SelfTestError> {
+    for vector in VECTORS {
+        check(vector)?;
+    }
+    Ok(())
+}
+
+/// Run [`self_test`] at most once per process and panic if it fails.
+///
+/// Intended for libraries that need an on-first-use power-on self-test
+/// rather than an explicit call at a known startup point.
+#[cfg(all(feature = "std", feature = "self-test-on-first-use"))]
+pub fn ensure_self_test() {
+    use std::sync::Once;
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        self_test().expect("RIPEMD-160 self-test failed");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::self_test;
+
+    // Exercises whichever backend `Ripemd160` is built with, so this also
+    // catches a broken `asm` backend when run with `--features asm`.
+    #[test]
+    fn self_test_passes() {
+        assert_eq!(self_test(), Ok(()));
+    }
+
+    #[cfg(all(feature = "std", feature = "self-test-on-first-use"))]
+    #[test]
+    fn ensure_self_test_does_not_panic() {
+        super::ensure_self_test();
+    }
+}